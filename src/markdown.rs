@@ -1,12 +1,418 @@
-use pulldown_cmark::{html, Options, Parser};
+use chrono::{Local, NaiveDate};
+use pulldown_cmark::{html, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
 use serde::Deserialize;
 use slug::slugify;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
 use regex::Regex;
 use image::ImageReader;
 use reqwest;
 use image::GenericImageView;
 use std::time::Duration;
+use syntect::html::highlighted_html_for_string;
+use syntect::parsing::SyntaxSet;
+use syntect::highlighting::ThemeSet;
+use minify_html::{minify, Cfg};
+use url;
+
+/// Build-time configuration for Markdown rendering (syntax highlighting, etc.).
+#[derive(Clone, Debug)]
+pub struct MarkdownConfig {
+    /// Name of the `syntect` theme used for build-time syntax highlighting
+    /// (e.g. "InspiredGitHub", "base16-ocean.dark").
+    pub highlight_theme: String,
+    /// Widths (in pixels) to generate responsive WebP variants at. Widths
+    /// wider than the source image are skipped so nothing is ever upscaled.
+    pub image_widths: Vec<u32>,
+    /// Directory (under `docs/`) that resized WebP variants are written to,
+    /// keyed by a content hash of the source bytes so rebuilds are idempotent.
+    pub image_cache_dir: String,
+    /// Whether remote images (fetched over HTTP on every build) are put
+    /// through the same responsive pipeline as local images, with their
+    /// WebP variants cached locally, instead of being left as hotlinked
+    /// `<img>` tags. The downloaded source itself is not cached.
+    pub cache_remote_images: bool,
+    /// Minifies the generated HTML (whitespace, comments, inline
+    /// `<style>`/`<script>`). Disable for easier local debugging.
+    pub minify_html: bool,
+    /// The site's own host (e.g. "example.com"), used to tell external links
+    /// apart from links back to the site itself. Links are treated as
+    /// external whenever this is empty.
+    pub site_host: String,
+    /// Adds `target="_blank" rel="noopener noreferrer"` to external links.
+    pub external_link_new_tab: bool,
+    /// Adds `rel="nofollow"` to external links.
+    pub external_link_nofollow: bool,
+    /// Converts straight quotes, `--`/`---`, and `...` into their curly/dash/
+    /// ellipsis equivalents. Code spans are left untouched.
+    pub smart_punctuation: bool,
+}
+
+impl Default for MarkdownConfig {
+    fn default() -> Self {
+        Self {
+            highlight_theme: "InspiredGitHub".to_string(),
+            image_widths: vec![480, 960, 1440],
+            image_cache_dir: "docs/cache/images".to_string(),
+            cache_remote_images: false,
+            minify_html: true,
+            site_host: String::new(),
+            external_link_new_tab: false,
+            external_link_nofollow: false,
+            smart_punctuation: false,
+        }
+    }
+}
+
+/// Builds the `pulldown_cmark::Options` for a build, enabling the standard
+/// extensions plus smart punctuation when `config.smart_punctuation` is set.
+fn markdown_options(config: &MarkdownConfig) -> Options {
+    // Keep every extension the baseline parsed with `Options::all()`, only
+    // gating smart punctuation on the new config flag (default off).
+    let mut options = Options::all();
+    options.remove(Options::ENABLE_SMART_PUNCTUATION);
+
+    if config.smart_punctuation {
+        options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    }
+
+    options
+}
+
+/// Whether `href`'s host component matches `site_host` exactly (case
+/// insensitively). Unparseable hrefs and an empty `site_host` are treated as
+/// not matching, so the link is hardened as external.
+fn is_own_host(href: &str, site_host: &str) -> bool {
+    if site_host.is_empty() {
+        return false;
+    }
+
+    url::Url::parse(href)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.eq_ignore_ascii_case(site_host)))
+        .unwrap_or(false)
+}
+
+/// Adds `target="_blank"`/`rel="noopener noreferrer"` and/or `rel="nofollow"`
+/// to anchors whose `href` points off-site, per `config`. A no-op when
+/// neither hardening option is enabled.
+fn harden_external_links(
+    html_str: &str,
+    config: &MarkdownConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !config.external_link_new_tab && !config.external_link_nofollow {
+        return Ok(html_str.to_string());
+    }
+
+    let link_re = Regex::new(r#"<a\s+([^>]*?)href="(https?://[^"]+)"([^>]*)>"#)?;
+
+    Ok(link_re
+        .replace_all(html_str, |caps: &regex::Captures| {
+            let before = &caps[1];
+            let href = &caps[2];
+            let after = &caps[3];
+
+            if is_own_host(href, &config.site_host) {
+                return caps[0].to_string();
+            }
+
+            let mut rel_tokens: Vec<&str> = Vec::new();
+            let mut extra_attrs = String::new();
+
+            if config.external_link_new_tab {
+                extra_attrs.push_str(r#" target="_blank""#);
+                rel_tokens.push("noopener");
+                rel_tokens.push("noreferrer");
+            }
+            if config.external_link_nofollow {
+                rel_tokens.push("nofollow");
+            }
+            if !rel_tokens.is_empty() {
+                extra_attrs.push_str(&format!(r#" rel="{}""#, rel_tokens.join(" ")));
+            }
+
+            format!(r#"<a {}href="{}"{}{}>"#, before, href, after, extra_attrs)
+        })
+        .to_string())
+}
+
+/// Minifies generated HTML: collapses whitespace between block elements,
+/// strips comments, and compacts inline `<style>`/`<script>` content.
+fn minify_html_output(html_str: &str) -> String {
+    let mut cfg = Cfg::new();
+    cfg.minify_css = true;
+    cfg.minify_js = true;
+
+    let minified = minify(html_str.as_bytes(), &cfg);
+    String::from_utf8(minified).unwrap_or_else(|_| html_str.to_string())
+}
+
+/// A resized, re-encoded image variant ready to go into a `srcset`.
+struct ResponsiveImage {
+    /// Width and height of the original (un-resized) source image.
+    original_dims: (u32, u32),
+    /// URL of the variant closest to (but never above) `original_dims.0`,
+    /// suitable for the `<img src>` fallback.
+    main_src: String,
+    /// Fully assembled `srcset` attribute value, e.g. "a.webp 480w, b.webp 960w".
+    srcset: String,
+}
+
+/// Decodes `bytes`, downscales it to each configured width (skipping widths
+/// wider than the source so nothing is upscaled), re-encodes every variant to
+/// WebP, and writes them under `config.image_cache_dir` keyed by a content
+/// hash so repeated builds reuse the same files.
+fn build_responsive_image(bytes: &[u8], config: &MarkdownConfig) -> Option<ResponsiveImage> {
+    use std::hash::{Hash, Hasher};
+
+    let source = image::load_from_memory(bytes).ok()?;
+    let (orig_width, orig_height) = source.dimensions();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    let content_hash = format!("{:x}", hasher.finish());
+
+    let cache_dir = std::path::Path::new(&config.image_cache_dir);
+    fs::create_dir_all(cache_dir).ok()?;
+    let public_dir = config
+        .image_cache_dir
+        .trim_start_matches("docs")
+        .trim_start_matches('/');
+
+    let mut widths: Vec<u32> = config
+        .image_widths
+        .iter()
+        .copied()
+        .filter(|&w| w < orig_width)
+        .collect();
+    widths.push(orig_width);
+    widths.sort_unstable();
+    widths.dedup();
+
+    let mut srcset_parts = Vec::new();
+    let mut main_src = String::new();
+
+    for width in widths {
+        let file_name = format!("{}-{}.webp", content_hash, width);
+        let out_path = cache_dir.join(&file_name);
+        let url = format!("/{}/{}", public_dir, file_name);
+
+        if !out_path.exists() {
+            let variant = if width == orig_width {
+                source.clone()
+            } else {
+                let height = ((width as u64 * orig_height as u64) / orig_width as u64).max(1) as u32;
+                source.resize_exact(width, height, image::imageops::FilterType::Lanczos3)
+            };
+
+            if variant
+                .save_with_format(&out_path, image::ImageFormat::WebP)
+                .is_err()
+            {
+                continue;
+            }
+        }
+
+        if width == orig_width {
+            main_src = url.clone();
+        }
+        srcset_parts.push(format!("{} {}w", url, width));
+    }
+
+    if main_src.is_empty() {
+        main_src = srcset_parts.last()?.split_whitespace().next()?.to_string();
+    }
+
+    Some(ResponsiveImage {
+        original_dims: (orig_width, orig_height),
+        main_src,
+        srcset: srcset_parts.join(", "),
+    })
+}
+
+/// Walks a stream of Markdown events, replacing fenced code blocks with
+/// `syntect`-highlighted HTML so pages need no client-side highlighter.
+fn highlight_code_blocks<'a>(
+    parser: Parser<'a>,
+    config: &MarkdownConfig,
+) -> Vec<Event<'a>> {
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme_set = ThemeSet::load_defaults();
+    let theme = theme_set
+        .themes
+        .get(&config.highlight_theme)
+        .unwrap_or_else(|| &theme_set.themes["InspiredGitHub"]);
+
+    let mut events = Vec::new();
+    let mut in_code_block = false;
+    let mut code_lang = String::new();
+    let mut code_buf = String::new();
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+                in_code_block = true;
+                code_lang = lang.to_string();
+                code_buf.clear();
+            }
+            Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(_))) if in_code_block => {
+                let syntax = syntax_set
+                    .find_syntax_by_token(&code_lang)
+                    .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+
+                let highlighted =
+                    highlighted_html_for_string(&code_buf, &syntax_set, syntax, theme)
+                        .unwrap_or_else(|_| format!("<pre><code>{}</code></pre>", code_buf));
+
+                events.push(Event::Html(highlighted.into()));
+                in_code_block = false;
+            }
+            Event::Text(text) if in_code_block => {
+                code_buf.push_str(&text);
+            }
+            other => events.push(other),
+        }
+    }
+
+    events
+}
+
+/// Walks a stream of Markdown events, giving every heading an `id` anchor
+/// (and a self-link) and collecting `(level, text, id)` entries along the way.
+/// Returns the rewritten events plus the nested `<ul>` table of contents built
+/// from those entries.
+fn build_toc<'a>(events: Vec<Event<'a>>) -> (Vec<Event<'a>>, String) {
+    let mut seen_ids = HashSet::new();
+    let mut entries: Vec<(u8, String, String)> = Vec::new();
+    let mut out = Vec::with_capacity(events.len());
+
+    let mut in_heading = false;
+    let mut heading_level: u8 = 0;
+    let mut heading_text = String::new();
+    let mut heading_events: Vec<Event<'a>> = Vec::new();
+
+    for event in events {
+        match event {
+            Event::Start(Tag::Heading(level, ..)) => {
+                in_heading = true;
+                heading_level = heading_level_as_u8(level);
+                heading_text.clear();
+                heading_events.clear();
+            }
+            Event::End(Tag::Heading(..)) if in_heading => {
+                let id = unique_slug(&heading_text, &mut seen_ids);
+
+                let mut inner_html = String::new();
+                html::push_html(&mut inner_html, heading_events.drain(..));
+
+                out.push(Event::Html(
+                    format!(
+                        "<h{level} id=\"{id}\">{inner}<a class=\"heading-anchor\" href=\"#{id}\">#</a></h{level}>",
+                        level = heading_level,
+                        id = id,
+                        inner = inner_html,
+                    )
+                    .into(),
+                ));
+
+                entries.push((heading_level, heading_text.clone(), id));
+                in_heading = false;
+            }
+            Event::Text(text) if in_heading => {
+                heading_text.push_str(&text);
+                heading_events.push(Event::Text(text));
+            }
+            Event::Code(code) if in_heading => {
+                heading_text.push_str(&code);
+                heading_events.push(Event::Code(code));
+            }
+            other if in_heading => heading_events.push(other),
+            other => out.push(other),
+        }
+    }
+
+    (out, render_toc(&entries))
+}
+
+fn heading_level_as_u8(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Escapes `&`, `<`, `>`, and `"` so plain text can be safely interpolated
+/// into hand-assembled HTML (order matters: `&` first to avoid double-escaping).
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Slugifies `text`, appending `-1`, `-2`, ... to disambiguate collisions
+/// against everything already in `seen_ids`.
+fn unique_slug(text: &str, seen_ids: &mut HashSet<String>) -> String {
+    let base = slugify(text);
+    let mut candidate = base.clone();
+    let mut suffix = 1;
+
+    while seen_ids.contains(&candidate) {
+        candidate = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+
+    seen_ids.insert(candidate.clone());
+    candidate
+}
+
+/// Assembles `(level, text, id)` heading entries into a nested `<ul>` tree,
+/// using a stack of open levels so e.g. an `h3` nests under the previous `h2`.
+/// Level jumps (h1 straight to h3) are handled without panicking.
+fn render_toc(entries: &[(u8, String, String)]) -> String {
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::new();
+    let mut open_levels: Vec<u8> = Vec::new();
+
+    for (level, text, id) in entries {
+        let level = *level;
+
+        while open_levels.last().map_or(false, |&top| top > level) {
+            html.push_str("</li></ul>");
+            open_levels.pop();
+        }
+
+        if open_levels.last().map_or(true, |&top| top < level) {
+            html.push_str("<ul class=\"toc\">");
+            open_levels.push(level);
+        } else {
+            html.push_str("</li>");
+        }
+
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>",
+            id,
+            escape_html(text)
+        ));
+    }
+
+    // Every level left on the stack has an `<li>` still open (it was kept
+    // open either to hold a nested `<ul>` or as the last sibling at that
+    // level), so each one needs its own closing `</li></ul>` pair.
+    for _ in &open_levels {
+        html.push_str("</li></ul>");
+    }
+
+    html
+}
 
 /// Front matter for a typical blog post (includes date).
 #[derive(Clone, Debug, Deserialize)]
@@ -16,6 +422,11 @@ pub struct PostFrontMatter {
     pub author: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    /// Marks a post as a draft so it's excluded from a normal build.
+    pub draft: Option<bool>,
+    /// Tags the post carries, used to build the tag taxonomy pages.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Front matter for a generic page (like About).
@@ -37,6 +448,23 @@ pub struct Post {
     pub reading_time: usize,
     /// Destination file name (e.g. "docs/posts/my-title.html").
     pub file_name: String,
+    /// Nested `<ul>` table of contents built from the post's headings.
+    pub toc: String,
+    /// Front matter's `date`, parsed for draft/future-date checks.
+    pub date: NaiveDate,
+}
+
+impl Post {
+    /// Whether this post belongs in a normal build: not marked `draft: true`,
+    /// and not dated in the future. Callers that want drafts or future posts
+    /// (e.g. a preview build) should bypass this check explicitly.
+    pub fn is_published(&self) -> bool {
+        if self.front_matter.draft.unwrap_or(false) {
+            return false;
+        }
+
+        self.date <= Local::now().date_naive()
+    }
 }
 
 /// Represents a generic page (e.g., About page).
@@ -60,7 +488,10 @@ pub struct Page {
 /// # My Post Content
 /// ```
 
-pub fn parse_post_markdown(file_path: &str) -> Result<Post, Box<dyn std::error::Error>> {
+pub fn parse_post_markdown(
+    file_path: &str,
+    markdown_config: &MarkdownConfig,
+) -> Result<Post, Box<dyn std::error::Error>> {
     let content = fs::read_to_string(file_path)?;
 
     // 1. Split off the leading '---\n'
@@ -83,16 +514,16 @@ pub fn parse_post_markdown(file_path: &str) -> Result<Post, Box<dyn std::error::
 
     // 4. Parse front matter with Serde
     let front_matter: PostFrontMatter = serde_yaml::from_str(front_matter_yaml)?;
+    let date = NaiveDate::parse_from_str(&front_matter.date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date '{}' in front matter: {}", front_matter.date, e))?;
 
-    // 5. Convert Markdown to HTML
+    // 5. Convert Markdown to HTML, highlighting fenced code blocks and anchoring
+    //    headings (for the table of contents) at build time
     let mut html_output = String::new();
-    let parser = Parser::new_ext(markdown_body, Options::all());
-    html::push_html(&mut html_output, parser);
-
-    let html_output = html_output.replace(
-        "<pre><code class=\"language-",
-        "<pre class=\"line-numbers\"><code class=\"language-",
-    );
+    let parser = Parser::new_ext(markdown_body, markdown_options(markdown_config));
+    let events = highlight_code_blocks(parser, markdown_config);
+    let (events, toc) = build_toc(events);
+    html::push_html(&mut html_output, events.into_iter());
 
     // 6. Calculate estimated reading time (assume ~200 words/min)
     let word_count = markdown_body.split_whitespace().count();
@@ -119,6 +550,16 @@ pub fn parse_post_markdown(file_path: &str) -> Result<Post, Box<dyn std::error::
             match client.get(src).send() {
                 Ok(response) if response.status().is_success() => {
                     if let Ok(bytes) = response.bytes() {
+                        if markdown_config.cache_remote_images {
+                            if let Some(responsive) = build_responsive_image(&bytes, markdown_config) {
+                                let (w, h) = responsive.original_dims;
+                                return format!(
+                                    r#"<div class='shimmer aspect-ratio' style='--aspect-ratio:{} / {}'><img src="{}" srcset="{}" sizes="(max-width: 960px) 100vw, 960px" alt="{}" loading="lazy"/></div>"#,
+                                    w, h, responsive.main_src, responsive.srcset, alt
+                                );
+                            }
+                        }
+
                         if let Ok(img) = image::load_from_memory(&bytes) {
                             let dims = img.dimensions();
                             return format!(
@@ -139,6 +580,16 @@ pub fn parse_post_markdown(file_path: &str) -> Result<Post, Box<dyn std::error::
         let cleaned_src = src.trim_start_matches("../");
         let src_path = std::path::Path::new("docs").join(cleaned_src);
 
+        if let Ok(bytes) = fs::read(&src_path) {
+            if let Some(responsive) = build_responsive_image(&bytes, markdown_config) {
+                let (w, h) = responsive.original_dims;
+                return format!(
+                    r#"<div class='shimmer aspect-ratio' style='--aspect-ratio:{} / {}'><img src="{}" srcset="{}" sizes="(max-width: 960px) 100vw, 960px" alt="{}" loading="lazy"/></div>"#,
+                    w, h, responsive.main_src, responsive.srcset, alt
+                );
+            }
+        }
+
         if let Ok(img) = ImageReader::open(&src_path) {
             let dims = img.into_dimensions().unwrap_or((0, 0));
             return format!(
@@ -151,11 +602,21 @@ pub fn parse_post_markdown(file_path: &str) -> Result<Post, Box<dyn std::error::
         "".to_string()
     }).to_string();
 
+    let hardened_html = harden_external_links(&rewritten_html, markdown_config)?;
+
+    let content = if markdown_config.minify_html {
+        minify_html_output(&hardened_html)
+    } else {
+        hardened_html
+    };
+
     Ok(Post {
         front_matter,
-        content: rewritten_html,
+        content,
         reading_time,
         file_name,
+        toc,
+        date,
     })
 }
 
@@ -169,7 +630,10 @@ pub fn parse_post_markdown(file_path: &str) -> Result<Post, Box<dyn std::error::
 ///
 /// # About Content Here
 /// ```
-pub fn parse_page_markdown(file_path: &str) -> Result<Page, Box<dyn std::error::Error>> {
+pub fn parse_page_markdown(
+    file_path: &str,
+    markdown_config: &MarkdownConfig,
+) -> Result<Page, Box<dyn std::error::Error>> {
     let content = fs::read_to_string(file_path)?;
 
     // 1. Split off the leading '---\n'
@@ -193,15 +657,11 @@ pub fn parse_page_markdown(file_path: &str) -> Result<Page, Box<dyn std::error::
     // 4. Parse front matter with Serde
     let front_matter: PageFrontMatter = serde_yaml::from_str(front_matter_yaml)?;
 
-    // 5. Convert Markdown to HTML
+    // 5. Convert Markdown to HTML, highlighting fenced code blocks at build time
     let mut html_output = String::new();
-    let parser = Parser::new_ext(markdown_body, Options::all());
-    html::push_html(&mut html_output, parser);
-
-    let html_output = html_output.replace(
-        "<pre><code class=\"language-",
-        "<pre class=\"line-numbers\"><code class=\"language-",
-    );
+    let parser = Parser::new_ext(markdown_body, markdown_options(markdown_config));
+    let events = highlight_code_blocks(parser, markdown_config);
+    html::push_html(&mut html_output, events.into_iter());
 
     // after you generate html_output
     let img_tag_re = Regex::new(r#"<img\s+[^>]*src="([^"]+)"\s+alt="([^"]*)".*?/?>"#)?;
@@ -220,6 +680,16 @@ pub fn parse_page_markdown(file_path: &str) -> Result<Page, Box<dyn std::error::
             match client.get(src).send() {
                 Ok(response) if response.status().is_success() => {
                     if let Ok(bytes) = response.bytes() {
+                        if markdown_config.cache_remote_images {
+                            if let Some(responsive) = build_responsive_image(&bytes, markdown_config) {
+                                let (w, h) = responsive.original_dims;
+                                return format!(
+                                    r#"<div class='shimmer aspect-ratio' style='--aspect-ratio:{} / {}'><img src="{}" srcset="{}" sizes="(max-width: 960px) 100vw, 960px" alt="{}" loading="lazy"/></div>"#,
+                                    w, h, responsive.main_src, responsive.srcset, alt
+                                );
+                            }
+                        }
+
                         if let Ok(img) = image::load_from_memory(&bytes) {
                             let dims = img.dimensions();
                             return format!(
@@ -240,6 +710,16 @@ pub fn parse_page_markdown(file_path: &str) -> Result<Page, Box<dyn std::error::
         let cleaned_src = src.trim_start_matches("../");
         let src_path = std::path::Path::new("docs").join(cleaned_src);
 
+        if let Ok(bytes) = fs::read(&src_path) {
+            if let Some(responsive) = build_responsive_image(&bytes, markdown_config) {
+                let (w, h) = responsive.original_dims;
+                return format!(
+                    r#"<div class='shimmer aspect-ratio' style='--aspect-ratio:{} / {}'><img src="{}" srcset="{}" sizes="(max-width: 960px) 100vw, 960px" alt="{}" loading="lazy"/></div>"#,
+                    w, h, responsive.main_src, responsive.srcset, alt
+                );
+            }
+        }
+
         if let Ok(img) = ImageReader::open(&src_path) {
             let dims = img.into_dimensions().unwrap_or((0, 0));
             return format!(
@@ -252,8 +732,168 @@ pub fn parse_page_markdown(file_path: &str) -> Result<Page, Box<dyn std::error::
         "".to_string()
     }).to_string();
 
+    let hardened_html = harden_external_links(&rewritten_html, markdown_config)?;
+
+    let content = if markdown_config.minify_html {
+        minify_html_output(&hardened_html)
+    } else {
+        hardened_html
+    };
+
     Ok(Page {
         front_matter,
-        content: rewritten_html,
+        content,
+    })
+}
+
+/// Groups posts by a front-matter term (tags, category, ...) keyed by the
+/// slugified term, reusing `slugify` so term URLs match post URLs.
+fn group_posts_by_term(
+    posts: &[Post],
+    terms_of: impl Fn(&Post) -> Vec<String>,
+) -> HashMap<String, Vec<Post>> {
+    let mut grouped: HashMap<String, Vec<Post>> = HashMap::new();
+
+    for post in posts {
+        for term in terms_of(post) {
+            grouped.entry(slugify(&term)).or_default().push(post.clone());
+        }
+    }
+
+    grouped
+}
+
+/// Groups posts by the tags in their front matter.
+pub fn group_posts_by_tag(posts: &[Post]) -> HashMap<String, Vec<Post>> {
+    group_posts_by_term(posts, |post| post.front_matter.tags.clone())
+}
+
+/// Groups posts by their front matter `category`.
+pub fn group_posts_by_category(posts: &[Post]) -> HashMap<String, Vec<Post>> {
+    group_posts_by_term(posts, |post| {
+        post.front_matter.category.clone().into_iter().collect()
     })
 }
+
+/// Writes one taxonomy index page per term under `docs/<kind>/<slug>.html`
+/// (each listing every post carrying that term) plus a `docs/<kind>/index.html`
+/// overview linking to all of them.
+fn write_taxonomy_pages(
+    kind: &str,
+    grouped: &HashMap<String, Vec<Post>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let taxonomy_dir = std::path::Path::new("docs").join(kind);
+    fs::create_dir_all(&taxonomy_dir)?;
+
+    let mut slugs: Vec<&String> = grouped.keys().collect();
+    slugs.sort();
+
+    let mut overview = format!("<h1>{}</h1><ul class=\"taxonomy-cloud\">", kind);
+    for slug in &slugs {
+        let term_posts = &grouped[*slug];
+
+        let mut page = format!("<h1>{}</h1><ul>", slug);
+        for post in term_posts {
+            page.push_str(&format!(
+                "<li><a href=\"/{}\">{}</a></li>",
+                post.file_name.trim_start_matches("docs/"),
+                escape_html(&post.front_matter.title)
+            ));
+        }
+        page.push_str("</ul>");
+        fs::write(taxonomy_dir.join(format!("{}.html", slug)), page)?;
+
+        overview.push_str(&format!(
+            "<li><a href=\"/{kind}/{slug}.html\">{slug}</a> ({count})</li>",
+            kind = kind,
+            slug = slug,
+            count = term_posts.len()
+        ));
+    }
+    overview.push_str("</ul>");
+    fs::write(taxonomy_dir.join("index.html"), overview)?;
+
+    Ok(())
+}
+
+/// Generates the `docs/tags/*` and `docs/categories/*` taxonomy pages for a
+/// full set of posts, mirroring Zola's taxonomy feature.
+pub fn write_taxonomies(posts: &[Post]) -> Result<(), Box<dyn std::error::Error>> {
+    write_taxonomy_pages("tags", &group_posts_by_tag(posts))?;
+    write_taxonomy_pages("categories", &group_posts_by_category(posts))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_toc_nests_siblings_flat() {
+        let entries = vec![
+            (1, "A".to_string(), "a".to_string()),
+            (1, "B".to_string(), "b".to_string()),
+        ];
+        assert_eq!(
+            render_toc(&entries),
+            "<ul class=\"toc\"><li><a href=\"#a\">A</a></li><li><a href=\"#b\">B</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn render_toc_handles_level_jumps_without_panicking() {
+        let entries = vec![
+            (1, "H1".to_string(), "h1".to_string()),
+            (3, "H3".to_string(), "h3".to_string()),
+            (1, "H1 Again".to_string(), "h1-again".to_string()),
+        ];
+        let html = render_toc(&entries);
+        assert_eq!(html.matches("<li>").count(), html.matches("</li>").count());
+        assert_eq!(html.matches("<ul").count(), html.matches("</ul>").count());
+    }
+
+    #[test]
+    fn render_toc_escapes_heading_text() {
+        let entries = vec![(1, "Comparing <T> & U".to_string(), "comparing-t-u".to_string())];
+        let html = render_toc(&entries);
+        assert!(html.contains("Comparing &lt;T&gt; &amp; U"));
+        assert!(!html.contains("<T>"));
+    }
+
+    #[test]
+    fn unique_slug_dedups_collisions() {
+        let mut seen = HashSet::new();
+        assert_eq!(unique_slug("Introduction", &mut seen), "introduction");
+        assert_eq!(unique_slug("Introduction", &mut seen), "introduction-1");
+        assert_eq!(unique_slug("Introduction", &mut seen), "introduction-2");
+    }
+
+    #[test]
+    fn is_own_host_matches_exact_host_only() {
+        assert!(is_own_host("https://example.com/page", "example.com"));
+        assert!(!is_own_host("https://notexample.com/", "example.com"));
+        assert!(!is_own_host("https://evil.com/?ref=example.com", "example.com"));
+    }
+
+    #[test]
+    fn is_own_host_rejects_malformed_hrefs_and_empty_site_host() {
+        assert!(!is_own_host("not a url", "example.com"));
+        assert!(!is_own_host("https://example.com/", ""));
+    }
+
+    #[test]
+    fn harden_external_links_only_touches_external_anchors() {
+        let config = MarkdownConfig {
+            site_host: "example.com".to_string(),
+            external_link_new_tab: true,
+            external_link_nofollow: true,
+            ..MarkdownConfig::default()
+        };
+        let html = r#"<a href="https://example.com/a">A</a><a href="https://other.com/b">B</a>"#;
+        let hardened = harden_external_links(html, &config).unwrap();
+
+        assert!(hardened.contains(r#"<a href="https://example.com/a">A</a>"#));
+        assert!(hardened.contains(r#"target="_blank""#));
+        assert!(hardened.contains(r#"rel="noopener noreferrer nofollow""#));
+    }
+}